@@ -1,12 +1,30 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod debounce;
+
+use debounce::Debouncer;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::path::Path;
 use std::fs;
+use std::time::Duration;
 use tauri::Emitter;
+use tokio::sync::Semaphore;
+use tokio_util::codec::{BytesCodec, FramedRead};
+
+const DEFAULT_CONCURRENCY: usize = 4;
+/// Files larger than this are skipped rather than uploaded, so one oversized
+/// document can't blow up memory or a single request.
+const DEFAULT_MAX_FILE_SIZE_BYTES: u64 = 200 * 1024 * 1024;
+/// Chunk size for the streamed multipart body; keeps peak memory flat
+/// regardless of file size.
+const UPLOAD_CHUNK_SIZE: usize = 64 * 1024;
+/// How long a path must be quiet before it's dispatched for ingestion, same
+/// default as the CLI watcher.
+const DEFAULT_DEBOUNCE_MS: u64 = 1000;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct Config {
@@ -15,6 +33,10 @@ struct Config {
     tenant_id: Option<String>,
     tenant_slug: Option<String>,
     folders: Vec<String>,
+    #[serde(default)]
+    concurrency: Option<usize>,
+    #[serde(default)]
+    max_file_size_bytes: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -23,10 +45,15 @@ struct IngestionStatus {
     processed_files: usize,
     current_file: Option<String>,
     is_running: bool,
+    bytes_uploaded: u64,
 }
 
 type AppState = Arc<Mutex<HashMap<String, String>>>;
 type StatusState = Arc<Mutex<IngestionStatus>>;
+/// Holds the live `notify` watcher while it's running. Replacing it with `None`
+/// (done by `stop_watching`) drops the watcher, which tears down its backing
+/// threads and ends the event stream it feeds.
+type WatcherState = Arc<Mutex<Option<RecommendedWatcher>>>;
 
 #[derive(Clone, serde::Serialize)]
 struct AuthResult {
@@ -91,14 +118,156 @@ async fn get_ingestion_status(status: tauri::State<'_, StatusState>) -> Result<I
     Ok(status.clone())
 }
 
+/// Uploads a single file to the backend. Shared by the initial backfill pass,
+/// the live watcher, and the one-shot `scan_once` command so all three ingest
+/// the same way. The file is streamed in fixed-size chunks rather than read
+/// into memory up front, so peak memory stays flat regardless of file size.
+/// Returns the number of bytes uploaded, or `0` if the file was skipped, could
+/// not be sent, or the backend answered with an error status.
+async fn upload_file(
+    file_path: &str,
+    client: &reqwest::Client,
+    backend_url: &str,
+    api_key: &str,
+    tenant_id: &str,
+    max_file_size_bytes: u64,
+) -> u64 {
+    let metadata = match tokio::fs::metadata(file_path).await {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            println!("✗ Could not read {}: {}", file_path, e);
+            return 0;
+        }
+    };
+
+    let file_size = metadata.len();
+    if file_size > max_file_size_bytes {
+        println!(
+            "✗ Skipping {}: {} bytes exceeds the {} byte limit",
+            file_path, file_size, max_file_size_bytes
+        );
+        return 0;
+    }
+
+    let file = match tokio::fs::File::open(file_path).await {
+        Ok(file) => file,
+        Err(e) => {
+            println!("✗ Could not read {}: {}", file_path, e);
+            return 0;
+        }
+    };
+
+    let url = format!("{}/api/ingest", backend_url);
+
+    // Determine file type
+    let file_type = if file_path.ends_with(".pdf") {
+        "application/pdf"
+    } else if file_path.ends_with(".docx") {
+        "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+    } else if file_path.ends_with(".txt") {
+        "text/plain"
+    } else if file_path.ends_with(".md") {
+        "text/markdown"
+    } else {
+        "application/octet-stream"
+    };
+
+    // Create form with file
+    let file_name = Path::new(file_path)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown");
+
+    let stream = FramedRead::with_capacity(file, BytesCodec::new(), UPLOAD_CHUNK_SIZE);
+    let body = reqwest::Body::wrap_stream(stream);
+
+    let part = match reqwest::multipart::Part::stream_with_length(body, file_size)
+        .file_name(file_name.to_string())
+        .mime_str(file_type)
+    {
+        Ok(part) => part,
+        Err(e) => {
+            println!("✗ Invalid mime type for {}: {}", file_path, e);
+            return 0;
+        }
+    };
+
+    let form = reqwest::multipart::Form::new()
+        .part("file", part)
+        .text("tenantId", tenant_id.to_string());
+
+    match client
+        .post(&url)
+        .header("x-api-key", api_key)
+        .multipart(form)
+        .send()
+        .await
+    {
+        Ok(response) => {
+            let status = response.status();
+            if status.is_success() {
+                println!("✓ Ingested: {}", file_path);
+                file_size
+            } else {
+                let body = response.text().await.unwrap_or_else(|_| "Unable to read response".to_string());
+                println!("✗ Failed to ingest {}: {} - {}", file_path, status, body);
+                0
+            }
+        }
+        Err(e) => {
+            println!("✗ Error ingesting {}: {}", file_path, e);
+            0
+        }
+    }
+}
+
+/// Uploads `file_path` and updates/emits `IngestionStatus` for it. The status
+/// mutex makes the counter update atomic across tasks completing out of order.
+async fn ingest_and_report(
+    app: &tauri::AppHandle,
+    status: &StatusState,
+    file_path: String,
+    client: &reqwest::Client,
+    backend_url: &str,
+    api_key: &str,
+    tenant_id: &str,
+    max_file_size_bytes: u64,
+) {
+    {
+        let mut status = status.lock().unwrap();
+        status.current_file = Some(file_path.clone());
+    }
+
+    println!("Processing: {}", file_path);
+    let uploaded = upload_file(&file_path, client, backend_url, api_key, tenant_id, max_file_size_bytes).await;
+
+    let progress = {
+        let mut status = status.lock().unwrap();
+        status.processed_files += 1;
+        status.bytes_uploaded += uploaded;
+        IngestionStatus {
+            total_files: status.total_files,
+            processed_files: status.processed_files,
+            current_file: Some(file_path.clone()),
+            is_running: status.is_running,
+            bytes_uploaded: status.bytes_uploaded,
+        }
+    };
+    let _ = app.emit("ingestion-progress", progress);
+}
+
 #[tauri::command]
 async fn start_watching(
     app: tauri::AppHandle,
     status: tauri::State<'_, StatusState>,
+    watcher_state: tauri::State<'_, WatcherState>,
     backend_url: String,
     api_key: String,
     tenant_id: String,
     folders: Vec<String>,
+    concurrency: Option<usize>,
+    max_file_size_bytes: Option<u64>,
+    debounce_ms: Option<u64>,
 ) -> Result<(), String> {
     // Set running status
     {
@@ -106,34 +275,41 @@ async fn start_watching(
         status.is_running = true;
         status.total_files = 0;
         status.processed_files = 0;
+        status.bytes_uploaded = 0;
     }
-    
-    // Clone necessary data for background task
+
     let status_clone = status.inner().clone();
-    
+    let watcher_state = watcher_state.inner().clone();
+    let concurrency = concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1);
+    let max_file_size_bytes = max_file_size_bytes.unwrap_or(DEFAULT_MAX_FILE_SIZE_BYTES);
+    let debounce_ms = debounce_ms.unwrap_or(DEFAULT_DEBOUNCE_MS);
+
     // Start file processing in background
     tauri::async_runtime::spawn(async move {
         println!("Started watching {} folders", folders.len());
-        
-        // Collect all files first
+
+        // Collect all files first for the initial backfill pass
         let mut all_files = Vec::new();
         for folder in &folders {
             if let Ok(files) = collect_files(&folder) {
                 all_files.extend(files);
             }
         }
-        
-        // Update total files count
+
         {
             let mut status = status_clone.lock().unwrap();
             status.total_files = all_files.len();
         }
-        
-        println!("Found {} files to process", all_files.len());
-        
-        // Process each file
+
+        println!("Found {} files to process ({} concurrent uploads)", all_files.len(), concurrency);
+
+        // Reused across uploads instead of building a new client per file, and
+        // bounded by a semaphore so at most `concurrency` uploads run at once.
+        let client = reqwest::Client::new();
+        let semaphore = Arc::new(Semaphore::new(concurrency));
+        let mut tasks = Vec::new();
+
         for file_path in all_files {
-            // Check if stop was requested
             {
                 let status = status_clone.lock().unwrap();
                 if !status.is_running {
@@ -141,101 +317,131 @@ async fn start_watching(
                     break;
                 }
             }
-            
-            {
-                let mut status = status_clone.lock().unwrap();
-                status.current_file = Some(file_path.clone());
-            }
-            
-            println!("Processing: {}", file_path);
-            
-            // Read file content
-            if let Ok(content) = fs::read(&file_path) {
-                // Send to backend
-                let client = reqwest::Client::new();
-                let url = format!("{}/api/ingest", backend_url);
-                
-                // Determine file type
-                let file_type = if file_path.ends_with(".pdf") {
-                    "application/pdf"
-                } else if file_path.ends_with(".docx") {
-                    "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
-                } else if file_path.ends_with(".txt") {
-                    "text/plain"
-                } else if file_path.ends_with(".md") {
-                    "text/markdown"
-                } else {
-                    "application/octet-stream"
-                };
-                
-                // Create form with file
-                let file_name = Path::new(&file_path)
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("unknown");
-                
-                let part = reqwest::multipart::Part::bytes(content)
-                    .file_name(file_name.to_string())
-                    .mime_str(file_type)
-                    .unwrap();
-                
-                let form = reqwest::multipart::Form::new()
-                    .part("file", part)
-                    .text("tenantId", tenant_id.clone());
-                
-                match client
-                    .post(&url)
-                    .header("x-api-key", &api_key)
-                    .multipart(form)
-                    .send()
-                    .await
-                {
-                    Ok(response) => {
-                        let status = response.status();
-                        if status.is_success() {
-                            println!("✓ Ingested: {}", file_path);
-                        } else {
-                            let body = response.text().await.unwrap_or_else(|_| "Unable to read response".to_string());
-                            println!("✗ Failed to ingest {}: {} - {}", file_path, status, body);
-                        }
-                    }
-                    Err(e) => {
-                        println!("✗ Error ingesting {}: {}", file_path, e);
-                    }
-                }
+
+            let semaphore = Arc::clone(&semaphore);
+            let client = client.clone();
+            let backend_url = backend_url.clone();
+            let api_key = api_key.clone();
+            let tenant_id = tenant_id.clone();
+            let status_clone = Arc::clone(&status_clone);
+            let app = app.clone();
+
+            tasks.push(tauri::async_runtime::spawn(async move {
+                let _permit = semaphore.acquire().await.unwrap();
+                ingest_and_report(&app, &status_clone, file_path, &client, &backend_url, &api_key, &tenant_id, max_file_size_bytes).await;
+            }));
+        }
+
+        for task in tasks {
+            let _ = task.await;
+        }
+
+        {
+            let status = status_clone.lock().unwrap();
+            if !status.is_running {
+                println!("⏸️  Backfill stopped; not starting live watching");
+                return;
             }
-            
-            // Update processed count
-            {
+        }
+
+        println!("Backfill complete, switching to live watching");
+
+        // Live watching: after the backfill, keep watching the configured
+        // folders and ingest new/modified files as they appear, the same way
+        // the CLI watcher does. The watcher is stashed in `watcher_state` so
+        // `stop_watching` can drop it to tear the event stream down cleanly.
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut watcher: RecommendedWatcher = match Watcher::new(tx, notify::Config::default()) {
+            Ok(watcher) => watcher,
+            Err(e) => {
+                println!("✗ Could not start watcher: {}", e);
                 let mut status = status_clone.lock().unwrap();
-                status.processed_files += 1;
+                status.is_running = false;
+                return;
+            }
+        };
+
+        for folder in &folders {
+            if let Err(e) = watcher.watch(Path::new(folder), RecursiveMode::Recursive) {
+                println!("✗ Could not watch {}: {}", folder, e);
             }
-            
-            // Emit event to frontend
-            let _ = app.emit("ingestion-progress", IngestionStatus {
-                total_files: {
-                    let status = status_clone.lock().unwrap();
-                    status.total_files
-                },
-                processed_files: {
-                    let status = status_clone.lock().unwrap();
-                    status.processed_files
-                },
-                current_file: Some(file_path.clone()),
-                is_running: true,
-            });
         }
-        
-        // Mark as complete
+
+        *watcher_state.lock().unwrap() = Some(watcher);
+
+        // `stop_watching` may have run while the watcher was being set up and
+        // found nothing to drop; drop it here in that case.
+        if !status_clone.lock().unwrap().is_running {
+            watcher_state.lock().unwrap().take();
+            println!("⏸️  Stopped before live watching started");
+            return;
+        }
+
+        // Settled paths come out of the debouncer, already coalesced and
+        // confirmed stable, ready to dispatch for ingestion — the same
+        // pattern the CLI watcher uses via `debounce::Debouncer`, so an
+        // editor save or a large file still being written doesn't get
+        // ingested mid-write.
+        let (settled_tx, mut settled_rx) = tokio::sync::mpsc::unbounded_channel();
+        let debouncer = Debouncer::spawn(Duration::from_millis(debounce_ms), settled_tx);
+
         {
-            let mut status = status_clone.lock().unwrap();
-            status.is_running = false;
-            status.current_file = None;
+            let client = client.clone();
+            let backend_url = backend_url.clone();
+            let api_key = api_key.clone();
+            let tenant_id = tenant_id.clone();
+            let status_clone = Arc::clone(&status_clone);
+            let app = app.clone();
+            let semaphore = Arc::clone(&semaphore);
+
+            tauri::async_runtime::spawn(async move {
+                while let Some(path) = settled_rx.recv().await {
+                    let semaphore = Arc::clone(&semaphore);
+                    let client = client.clone();
+                    let backend_url = backend_url.clone();
+                    let api_key = api_key.clone();
+                    let tenant_id = tenant_id.clone();
+                    let status_clone = Arc::clone(&status_clone);
+                    let app = app.clone();
+
+                    tauri::async_runtime::spawn(async move {
+                        let _permit = semaphore.acquire().await.unwrap();
+                        ingest_and_report(&app, &status_clone, path, &client, &backend_url, &api_key, &tenant_id, max_file_size_bytes).await;
+                    });
+                }
+            });
         }
-        
-        println!("Finished processing all files");
+
+        // `rx` blocks until the watcher (and its sender) is dropped by
+        // `stop_watching`, so this thread exits cleanly on stop.
+        std::thread::spawn(move || {
+            for res in rx {
+                let event = match res {
+                    Ok(event) => event,
+                    Err(e) => {
+                        println!("Watch error: {:?}", e);
+                        continue;
+                    }
+                };
+
+                if !matches!(event.kind, notify::EventKind::Create(_) | notify::EventKind::Modify(_)) {
+                    continue;
+                }
+
+                for path in event.paths {
+                    let Some(path_str) = path.to_str().map(|s| s.to_string()) else {
+                        continue;
+                    };
+                    if !is_supported_file(&path_str) {
+                        continue;
+                    }
+
+                    debouncer.notify(path_str);
+                }
+            }
+        });
     });
-    
+
     Ok(())
 }
 
@@ -286,21 +492,83 @@ fn is_supported_file(path: &str) -> bool {
 }
 
 #[tauri::command]
-async fn stop_watching(status: tauri::State<'_, StatusState>) -> Result<(), String> {
-    let mut status = status.lock().unwrap();
-    status.is_running = false;
+async fn stop_watching(
+    status: tauri::State<'_, StatusState>,
+    watcher_state: tauri::State<'_, WatcherState>,
+) -> Result<(), String> {
+    {
+        let mut status = status.lock().unwrap();
+        status.is_running = false;
+    }
+    // Dropping the watcher tears down its event stream, ending the live-watch
+    // thread started by `start_watching`.
+    watcher_state.lock().unwrap().take();
     Ok(())
 }
 
+/// Performs a single `collect_files` + upload pass without starting a watcher.
 #[tauri::command]
-async fn scan_once(status: tauri::State<'_, StatusState>) -> Result<(), String> {
-    let mut status = status.lock().unwrap();
-    status.total_files = 0;
-    status.processed_files = 0;
-    
-    // Scan logic will go here
-    println!("Scanning folders once");
-    
+async fn scan_once(
+    app: tauri::AppHandle,
+    status: tauri::State<'_, StatusState>,
+    backend_url: String,
+    api_key: String,
+    tenant_id: String,
+    folders: Vec<String>,
+    concurrency: Option<usize>,
+    max_file_size_bytes: Option<u64>,
+) -> Result<(), String> {
+    let concurrency = concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1);
+    let max_file_size_bytes = max_file_size_bytes.unwrap_or(DEFAULT_MAX_FILE_SIZE_BYTES);
+
+    let mut all_files = Vec::new();
+    for folder in &folders {
+        if let Ok(files) = collect_files(&folder) {
+            all_files.extend(files);
+        }
+    }
+
+    {
+        let mut status = status.lock().unwrap();
+        status.is_running = true;
+        status.total_files = all_files.len();
+        status.processed_files = 0;
+        status.bytes_uploaded = 0;
+    }
+
+    println!("Scanning folders once: {} files found", all_files.len());
+
+    let client = reqwest::Client::new();
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let status_clone = status.inner().clone();
+    let mut tasks = Vec::new();
+
+    for file_path in all_files {
+        let semaphore = Arc::clone(&semaphore);
+        let client = client.clone();
+        let backend_url = backend_url.clone();
+        let api_key = api_key.clone();
+        let tenant_id = tenant_id.clone();
+        let status_clone = Arc::clone(&status_clone);
+        let app = app.clone();
+
+        tasks.push(tauri::async_runtime::spawn(async move {
+            let _permit = semaphore.acquire().await.unwrap();
+            ingest_and_report(&app, &status_clone, file_path, &client, &backend_url, &api_key, &tenant_id, max_file_size_bytes).await;
+        }));
+    }
+
+    for task in tasks {
+        let _ = task.await;
+    }
+
+    {
+        let mut status = status.lock().unwrap();
+        status.is_running = false;
+        status.current_file = None;
+    }
+
+    println!("Scan complete");
     Ok(())
 }
 
@@ -310,7 +578,9 @@ fn main() {
         processed_files: 0,
         current_file: None,
         is_running: false,
+        bytes_uploaded: 0,
     }));
+    let watcher_state: WatcherState = Arc::new(Mutex::new(None));
 
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
@@ -318,6 +588,7 @@ fn main() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .manage(status_state)
+        .manage(watcher_state)
         .invoke_handler(tauri::generate_handler![
             authenticate,
             get_ingestion_status,
@@ -0,0 +1,208 @@
+//! Throughput benchmark driven by a JSON workload file.
+//!
+//! Generates a synthetic corpus matching the workload's size distribution and
+//! format mix, ingests it against the configured backend, and reports
+//! throughput and latency as JSON. Bypasses the ingest queue entirely so a
+//! benchmark run never pollutes dedup/retry state kept for real files.
+
+use crate::{Config, IngestPayload};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+#[derive(Debug, Deserialize)]
+struct Workload {
+    file_count: usize,
+    min_size_bytes: usize,
+    max_size_bytes: usize,
+    formats: Vec<String>,
+    #[serde(default = "default_concurrency")]
+    concurrency: usize,
+    #[serde(default)]
+    results_endpoint: Option<String>,
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+fn load_workload(path: &Path) -> Result<Workload> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read workload file {:?}", path))?;
+    serde_json::from_str(&raw).with_context(|| format!("Invalid workload file {:?}", path))
+}
+
+struct SyntheticFile {
+    file_name: String,
+    content: String,
+}
+
+/// Builds an in-memory corpus matching the workload's size distribution and
+/// format mix. Content is filler text regardless of extension, since the
+/// backend only needs bytes to ingest, not a real PDF/DOCX body.
+fn generate_corpus(workload: &Workload) -> Vec<SyntheticFile> {
+    let formats = if workload.formats.is_empty() {
+        vec!["txt".to_string()]
+    } else {
+        workload.formats.clone()
+    };
+
+    (0..workload.file_count)
+        .map(|i| {
+            let format = &formats[i % formats.len()];
+            let size = size_for(workload, i);
+            SyntheticFile {
+                file_name: format!("bench-{:05}.{}", i, format),
+                content: "x".repeat(size),
+            }
+        })
+        .collect()
+}
+
+/// Spreads sizes evenly across `[min_size_bytes, max_size_bytes]`, avoiding a
+/// dependency on a random number generator for what's meant to be a
+/// repeatable benchmark.
+fn size_for(workload: &Workload, index: usize) -> usize {
+    if workload.file_count <= 1 || workload.max_size_bytes <= workload.min_size_bytes {
+        return workload.min_size_bytes;
+    }
+    let span = workload.max_size_bytes - workload.min_size_bytes;
+    let step = span / (workload.file_count - 1).max(1);
+    workload.min_size_bytes + step * (index % workload.file_count)
+}
+
+#[derive(Debug, Serialize)]
+struct BenchReport {
+    hostname: String,
+    cpu_count: usize,
+    crate_version: String,
+    files_sent: usize,
+    failures: usize,
+    duration_secs: f64,
+    files_per_sec: f64,
+    bytes_per_sec: f64,
+    p50_latency_ms: f64,
+    p95_latency_ms: f64,
+}
+
+/// Runs ingestion against a synthetic corpus generated from `workload_path`
+/// and prints a [`BenchReport`] as JSON, optionally also POSTing it to the
+/// workload's `results_endpoint`.
+pub async fn run(config: &Config, workload_path: &Path) -> Result<()> {
+    let workload = load_workload(workload_path)?;
+    let corpus = generate_corpus(&workload);
+    let total_bytes: usize = corpus.iter().map(|file| file.content.len()).sum();
+
+    println!(
+        "Benchmarking {} files against {} ({} concurrent)",
+        corpus.len(),
+        config.backend_url,
+        workload.concurrency
+    );
+
+    let client = reqwest::Client::new();
+    let semaphore = Arc::new(Semaphore::new(workload.concurrency.max(1)));
+    let url = format!("{}/api/ingest", config.backend_url);
+
+    let start = Instant::now();
+    let mut tasks = Vec::with_capacity(corpus.len());
+
+    for file in corpus {
+        let semaphore = Arc::clone(&semaphore);
+        let client = client.clone();
+        let url = url.clone();
+        let tenant_slug = config.tenant_slug.clone();
+        let api_key = config.api_key.clone();
+
+        tasks.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire().await.expect("semaphore closed");
+            let payload = IngestPayload {
+                tenant_slug,
+                api_key,
+                file_name: file.file_name,
+                doc_type_hint: "other".to_string(),
+                content: file.content,
+            };
+
+            let request_start = Instant::now();
+            let result = client.post(&url).json(&payload).send().await;
+            let latency = request_start.elapsed();
+            let ok = matches!(&result, Ok(response) if response.status().is_success());
+            (ok, latency)
+        }));
+    }
+
+    let mut latencies = Vec::with_capacity(tasks.len());
+    let mut failures = 0usize;
+    for task in tasks {
+        let (ok, latency) = task.await.context("Bench request task panicked")?;
+        latencies.push(latency);
+        if !ok {
+            failures += 1;
+        }
+    }
+
+    let report = build_report(&latencies, failures, total_bytes, start.elapsed());
+    let report_json = serde_json::to_string_pretty(&report)?;
+    println!("{}", report_json);
+
+    if let Some(endpoint) = &workload.results_endpoint {
+        client
+            .post(endpoint)
+            .json(&report)
+            .send()
+            .await
+            .with_context(|| format!("Could not post results to {}", endpoint))?;
+    }
+
+    Ok(())
+}
+
+fn build_report(
+    latencies: &[Duration],
+    failures: usize,
+    total_bytes: usize,
+    duration: Duration,
+) -> BenchReport {
+    let files_sent = latencies.len();
+    let duration_secs = duration.as_secs_f64();
+
+    let mut sorted = latencies.to_vec();
+    sorted.sort();
+
+    BenchReport {
+        hostname: hostname::get()
+            .map(|h| h.to_string_lossy().to_string())
+            .unwrap_or_else(|_| "unknown".to_string()),
+        cpu_count: std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1),
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        files_sent,
+        failures,
+        duration_secs,
+        files_per_sec: if duration_secs > 0.0 {
+            files_sent as f64 / duration_secs
+        } else {
+            0.0
+        },
+        bytes_per_sec: if duration_secs > 0.0 {
+            total_bytes as f64 / duration_secs
+        } else {
+            0.0
+        },
+        p50_latency_ms: percentile_ms(&sorted, 0.50),
+        p95_latency_ms: percentile_ms(&sorted, 0.95),
+    }
+}
+
+fn percentile_ms(sorted_latencies: &[Duration], p: f64) -> f64 {
+    if sorted_latencies.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted_latencies.len() as f64 - 1.0) * p).round() as usize;
+    sorted_latencies[index].as_secs_f64() * 1000.0
+}
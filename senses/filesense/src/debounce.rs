@@ -0,0 +1,73 @@
+//! Coalesces bursty filesystem events into a single dispatch per settled path.
+//!
+//! A single editor save can emit several `notify` modify events, and a large
+//! file still being written shouldn't be ingested half-finished. The debouncer
+//! buffers incoming paths and only forwards one once it has been quiet for
+//! `window`, after confirming its size is stable across two samples.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::time::interval;
+
+pub struct Debouncer {
+    tx: mpsc::UnboundedSender<String>,
+}
+
+impl Debouncer {
+    /// Spawns the debounce loop and returns a handle to feed it paths. Settled
+    /// paths are sent to `out`.
+    pub fn spawn(window: Duration, out: mpsc::UnboundedSender<String>) -> Self {
+        let (tx, mut rx) = mpsc::unbounded_channel::<String>();
+
+        tokio::spawn(async move {
+            let mut pending: HashMap<String, Instant> = HashMap::new();
+            let mut tick = interval(Duration::from_millis(100));
+
+            loop {
+                tokio::select! {
+                    maybe_path = rx.recv() => {
+                        match maybe_path {
+                            Some(path) => {
+                                pending.insert(path, Instant::now());
+                            }
+                            None => break,
+                        }
+                    }
+                    _ = tick.tick() => {
+                        let now = Instant::now();
+                        let due: Vec<String> = pending
+                            .iter()
+                            .filter(|(_, seen)| now.duration_since(**seen) >= window)
+                            .map(|(path, _)| path.clone())
+                            .collect();
+
+                        for path in due {
+                            pending.remove(&path);
+                            if is_stable(&path).await {
+                                let _ = out.send(path);
+                            }
+                        }
+                    }
+                }
+            }
+        });
+
+        Self { tx }
+    }
+
+    /// Queues `path` for debounced dispatch, resetting its quiet timer if it was
+    /// already pending. Repeated events for the same path coalesce into one entry.
+    pub fn notify(&self, path: String) {
+        let _ = self.tx.send(path);
+    }
+}
+
+/// Confirms a file's size is unchanged across two samples a moment apart, so a
+/// file still being written isn't handed off mid-write.
+async fn is_stable(path: &str) -> bool {
+    let first = std::fs::metadata(path).map(|m| m.len());
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    let second = std::fs::metadata(path).map(|m| m.len());
+    matches!((first, second), (Ok(a), Ok(b)) if a == b)
+}
@@ -0,0 +1,104 @@
+//! Text extraction for the document formats `should_process` accepts.
+//!
+//! Extractors work from bytes rather than a filesystem path, since content can
+//! come from a remote `SourceFs` as well as the local disk. Plain text formats
+//! are passed through as-is; binary formats get a dedicated `Extractor`. New
+//! formats can be supported by adding an implementation and registering it in
+//! [`extractor_for`], without touching the watcher.
+
+use anyhow::{Context, Result};
+use std::io::{Cursor, Read};
+
+pub trait Extractor {
+    fn extract(&self, bytes: &[u8]) -> Result<String>;
+}
+
+struct PlainTextExtractor;
+
+impl Extractor for PlainTextExtractor {
+    fn extract(&self, bytes: &[u8]) -> Result<String> {
+        String::from_utf8(bytes.to_vec()).context("File is not valid UTF-8 text")
+    }
+}
+
+struct PdfExtractor;
+
+impl Extractor for PdfExtractor {
+    fn extract(&self, bytes: &[u8]) -> Result<String> {
+        pdf_extract::extract_text_from_mem(bytes).context("Could not extract text from PDF")
+    }
+}
+
+struct DocxExtractor;
+
+impl Extractor for DocxExtractor {
+    fn extract(&self, bytes: &[u8]) -> Result<String> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+            .context("Could not open DOCX as a zip archive")?;
+        let mut document_xml = archive
+            .by_name("word/document.xml")
+            .context("DOCX is missing word/document.xml")?;
+
+        let mut xml = String::new();
+        document_xml
+            .read_to_string(&mut xml)
+            .context("Could not read word/document.xml")?;
+
+        Ok(text_from_document_xml(&xml))
+    }
+}
+
+/// Strips OOXML markup, keeping the text inside `<w:t>` runs and turning
+/// paragraph breaks into newlines.
+fn text_from_document_xml(xml: &str) -> String {
+    use quick_xml::events::Event;
+    use quick_xml::reader::Reader;
+
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(false);
+
+    let mut text = String::new();
+    let mut in_text_run = false;
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"t" => in_text_run = true,
+            Ok(Event::End(e)) if e.local_name().as_ref() == b"t" => in_text_run = false,
+            Ok(Event::Start(e)) if e.local_name().as_ref() == b"p" => {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+            }
+            Ok(Event::Text(e)) if in_text_run => {
+                text.push_str(&e.unescape().unwrap_or_default());
+            }
+            Ok(Event::Eof) => break,
+            Err(_) => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    text
+}
+
+/// Looks up the extractor for a file name's extension. Returns `None` for
+/// extensions `should_process` doesn't accept, or that have no extractor.
+///
+/// Legacy `.doc` files are the pre-2007 OLE2 compound-file format, not a zip
+/// archive, so they can't go through [`DocxExtractor`]; there's no `.doc`
+/// extractor yet, so they return `None` rather than failing confusingly
+/// partway through a zip parse.
+pub fn extractor_for(file_name: &str) -> Option<Box<dyn Extractor>> {
+    let ext = std::path::Path::new(file_name)
+        .extension()?
+        .to_string_lossy()
+        .to_lowercase();
+    match ext.as_str() {
+        "txt" | "md" => Some(Box::new(PlainTextExtractor)),
+        "pdf" => Some(Box::new(PdfExtractor)),
+        "docx" => Some(Box::new(DocxExtractor)),
+        _ => None,
+    }
+}
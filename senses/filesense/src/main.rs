@@ -1,10 +1,22 @@
+mod bench;
+mod debounce;
+mod extract;
+mod queue;
+mod source;
+
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
+use debounce::Debouncer;
 use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use queue::IngestQueue;
 use serde::{Deserialize, Serialize};
+use source::{FolderSource, LocalFs, SftpAuth, SftpFs, SourceFs};
 use std::fs;
 use std::path::PathBuf;
 use std::sync::mpsc::channel;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Semaphore;
 
 #[derive(Parser)]
 #[command(name = "filesense")]
@@ -20,6 +32,11 @@ enum Commands {
     Run,
     /// Scan folders once and exit
     Once,
+    /// Benchmark ingestion throughput against a synthetic corpus
+    Bench {
+        /// Path to a JSON workload file describing the synthetic corpus
+        workload: PathBuf,
+    },
 }
 
 #[derive(Debug, Deserialize)]
@@ -28,6 +45,56 @@ struct Config {
     api_key: String,
     backend_url: String,
     folders: Vec<String>,
+    /// Maximum number of files uploaded concurrently.
+    #[serde(default = "default_concurrency")]
+    concurrency: usize,
+    /// How long a path must be quiet before it's dispatched for ingestion.
+    #[serde(default = "default_debounce_ms")]
+    debounce_ms: u64,
+    /// Password used to authenticate `sftp://` folders, if not using a key.
+    #[serde(default)]
+    sftp_password: Option<String>,
+    /// Private key path used to authenticate `sftp://` folders.
+    #[serde(default)]
+    sftp_key_path: Option<String>,
+    /// Files larger than this are skipped rather than ingested, so one oversized
+    /// document can't blow up memory during extraction.
+    #[serde(default = "default_max_file_size_bytes")]
+    max_file_size_bytes: u64,
+}
+
+fn default_concurrency() -> usize {
+    4
+}
+
+fn default_debounce_ms() -> u64 {
+    1000
+}
+
+fn default_max_file_size_bytes() -> u64 {
+    200 * 1024 * 1024
+}
+
+/// Shared state handed to every ingest task: the HTTP client is reused across
+/// uploads instead of being rebuilt per file, and the semaphore bounds how many
+/// uploads are in flight at once.
+struct IngestCtx {
+    config: Config,
+    queue: IngestQueue,
+    client: reqwest::Client,
+    semaphore: Semaphore,
+}
+
+impl IngestCtx {
+    fn new(config: Config, queue: IngestQueue) -> Self {
+        let semaphore = Semaphore::new(config.concurrency.max(1));
+        Self {
+            config,
+            queue,
+            client: reqwest::Client::new(),
+            semaphore,
+        }
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -47,13 +114,37 @@ async fn main() -> Result<()> {
     let config = load_config()?;
 
     match cli.command {
-        Commands::Run => run_watcher(&config).await?,
-        Commands::Once => scan_once(&config).await?,
+        Commands::Run => run_watcher(config).await?,
+        Commands::Once => scan_once(config).await?,
+        Commands::Bench { workload } => bench::run(&config, &workload).await?,
     }
 
     Ok(())
 }
 
+/// Resolves a configured folder entry to the filesystem that serves it: local
+/// paths go through `std::fs`, `sftp://` entries open an authenticated
+/// connection using the credentials in `config`.
+fn open_source(folder: &str, config: &Config) -> Result<(Box<dyn SourceFs>, String)> {
+    match source::parse_folder(folder) {
+        FolderSource::Local { root } => Ok((Box::new(LocalFs), root)),
+        FolderSource::Sftp {
+            host,
+            port,
+            username,
+            root,
+        } => {
+            let auth = SftpAuth {
+                username,
+                password: config.sftp_password.clone(),
+                key_path: config.sftp_key_path.clone(),
+            };
+            let fs = SftpFs::connect(&host, port, &auth)?;
+            Ok((Box::new(fs), root))
+        }
+    }
+}
+
 fn load_config() -> Result<Config> {
     let config_path = dirs::home_dir()
         .context("Could not find home directory")?
@@ -68,11 +159,64 @@ fn load_config() -> Result<Config> {
     Ok(config)
 }
 
-async fn run_watcher(config: &Config) -> Result<()> {
+/// An open `SourceFs`, mutex-guarded because a live SFTP session isn't safe to
+/// drive from more than one task at a time.
+type SourceHandle = Arc<tokio::sync::Mutex<Box<dyn SourceFs>>>;
+
+async fn run_watcher(config: Config) -> Result<()> {
     println!("Starting FileSense watcher...");
     println!("Backend: {}", config.backend_url);
     println!("Tenant: {}", config.tenant_slug);
     println!("Watching {} folders", config.folders.len());
+    println!("Upload concurrency: {}", config.concurrency);
+
+    let queue = IngestQueue::open(&queue::default_queue_path()?)?;
+    let ctx = Arc::new(IngestCtx::new(config, queue));
+    // The live `notify` watcher only supports local paths, so resumed work and
+    // filesystem events always go through a plain local source.
+    let local: SourceHandle = Arc::new(tokio::sync::Mutex::new(Box::new(LocalFs)));
+
+    // Resume any work left pending/failed from a previous run before picking up
+    // new filesystem events.
+    let resumable = ctx.queue.resumable_paths()?;
+    let handles: Vec<_> = resumable
+        .into_iter()
+        .filter(|path| std::path::Path::new(path).exists())
+        .map(|path| {
+            let ctx = Arc::clone(&ctx);
+            let local = Arc::clone(&local);
+            tokio::spawn(async move {
+                println!("Resuming pending ingest: {}", path);
+                if let Err(e) = ingest_with_retry(&path, &local, &ctx).await {
+                    eprintln!("Error ingesting file: {}", e);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        let _ = handle.await;
+    }
+
+    // Settled paths come out the other end of the debouncer, already coalesced
+    // and confirmed stable, ready to be dispatched for ingestion.
+    let (settled_tx, mut settled_rx) = tokio::sync::mpsc::unbounded_channel();
+    let debouncer = Debouncer::spawn(Duration::from_millis(ctx.config.debounce_ms), settled_tx);
+
+    {
+        let ctx = Arc::clone(&ctx);
+        let local = Arc::clone(&local);
+        tokio::spawn(async move {
+            while let Some(path) = settled_rx.recv().await {
+                let ctx = Arc::clone(&ctx);
+                let local = Arc::clone(&local);
+                tokio::spawn(async move {
+                    if let Err(e) = ingest_with_retry(&path, &local, &ctx).await {
+                        eprintln!("Error ingesting file: {}", e);
+                    }
+                });
+            }
+        });
+    }
 
     let (tx, rx) = channel();
     let mut watcher: RecommendedWatcher = Watcher::new(
@@ -80,8 +224,14 @@ async fn run_watcher(config: &Config) -> Result<()> {
         notify::Config::default(),
     )?;
 
-    // Watch all configured folders
-    for folder in &config.folders {
+    // Watch all configured folders. `notify` can only watch the local
+    // filesystem, so remote `sftp://` folders are skipped here; use `Once` to
+    // ingest them.
+    for folder in &ctx.config.folders {
+        if matches!(source::parse_folder(folder), FolderSource::Sftp { .. }) {
+            println!("  - {} (remote, not live-watched; use `Once` to scan it)", folder);
+            continue;
+        }
         println!("  - {}", folder);
         watcher.watch(folder.as_ref(), RecursiveMode::Recursive)?;
     }
@@ -90,7 +240,7 @@ async fn run_watcher(config: &Config) -> Result<()> {
 
     for res in rx {
         match res {
-            Ok(event) => handle_event(event, config).await?,
+            Ok(event) => handle_event(event, &debouncer),
             Err(e) => println!("Watch error: {:?}", e),
         }
     }
@@ -98,58 +248,80 @@ async fn run_watcher(config: &Config) -> Result<()> {
     Ok(())
 }
 
-async fn handle_event(event: Event, config: &Config) -> Result<()> {
+/// Feeds every `Create`/`Modify` path into the debouncer instead of ingesting it
+/// immediately, so repeated events for the same path (an editor save, a file
+/// still being written) coalesce into a single settle-then-ingest dispatch.
+fn handle_event(event: Event, debouncer: &Debouncer) {
     use notify::EventKind;
 
-    match event.kind {
-        EventKind::Create(_) | EventKind::Modify(_) => {
-            for path in event.paths {
-                if should_process(&path) {
-                    println!("Processing: {:?}", path);
-                    if let Err(e) = ingest_file(&path, config).await {
-                        eprintln!("Error ingesting file: {}", e);
-                    }
-                }
-            }
-        }
-        _ => {}
+    if !matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) {
+        return;
     }
 
-    Ok(())
+    for path in event.paths {
+        let Some(path) = path.to_str() else { continue };
+        if should_process(path) {
+            debouncer.notify(path.to_string());
+        }
+    }
 }
 
-async fn scan_once(config: &Config) -> Result<()> {
+async fn scan_once(config: Config) -> Result<()> {
     println!("Scanning folders once...");
+    println!("Upload concurrency: {}", config.concurrency);
+
+    let queue = IngestQueue::open(&queue::default_queue_path()?)?;
+    let folders = config.folders.clone();
+    let ctx = Arc::new(IngestCtx::new(config, queue));
+
+    for folder in &folders {
+        let (fs, root) = match open_source(folder, &ctx.config) {
+            Ok(opened) => opened,
+            Err(e) => {
+                eprintln!("Could not open folder {}: {}", folder, e);
+                continue;
+            }
+        };
+        let source: SourceHandle = Arc::new(tokio::sync::Mutex::new(fs));
+
+        let mut files = Vec::new();
+        collect_files(&source, &root, &mut files).await?;
+
+        let handles: Vec<_> = files
+            .into_iter()
+            .map(|path| {
+                let ctx = Arc::clone(&ctx);
+                let source = Arc::clone(&source);
+                tokio::spawn(async move {
+                    if let Err(e) = ingest_with_retry(&path, &source, &ctx).await {
+                        eprintln!("Error: {}", e);
+                    }
+                })
+            })
+            .collect();
 
-    for folder in &config.folders {
-        let folder_path = PathBuf::from(folder);
-        if !folder_path.exists() {
-            eprintln!("Folder does not exist: {}", folder);
-            continue;
+        for handle in handles {
+            let _ = handle.await;
         }
-
-        scan_folder(&folder_path, config).await?;
     }
 
     println!("Scan complete.");
     Ok(())
 }
 
-fn scan_folder<'a>(path: &'a PathBuf, config: &'a Config) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
+fn collect_files<'a>(
+    source: &'a SourceHandle,
+    path: &'a str,
+    out: &'a mut Vec<String>,
+) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<()>> + Send + 'a>> {
     Box::pin(async move {
-        let entries = fs::read_dir(path)?;
+        let entries = source.lock().await.read_dir(path).await?;
 
         for entry in entries {
-            let entry = entry?;
-            let path = entry.path();
-
-            if path.is_dir() {
-                scan_folder(&path, config).await?;
-            } else if should_process(&path) {
-                println!("Processing: {:?}", path);
-                if let Err(e) = ingest_file(&path, config).await {
-                    eprintln!("Error: {}", e);
-                }
+            if source.lock().await.is_dir(&entry).await? {
+                collect_files(source, &entry, out).await?;
+            } else if should_process(&entry) {
+                out.push(entry);
             }
         }
 
@@ -157,24 +329,82 @@ fn scan_folder<'a>(path: &'a PathBuf, config: &'a Config) -> std::pin::Pin<Box<d
     })
 }
 
-fn should_process(path: &PathBuf) -> bool {
-    if let Some(ext) = path.extension() {
-        let ext = ext.to_string_lossy().to_lowercase();
-        matches!(ext.as_str(), "txt" | "md" | "pdf" | "doc" | "docx")
-    } else {
-        false
+fn should_process(path: &str) -> bool {
+    match std::path::Path::new(path).extension() {
+        Some(ext) => {
+            let ext = ext.to_string_lossy().to_lowercase();
+            // Legacy `.doc` has no extractor (see `extract::extractor_for`) and is
+            // excluded here too, so it's skipped up front instead of entering a
+            // permanent fail/retry loop.
+            matches!(ext.as_str(), "txt" | "md" | "pdf" | "docx")
+        }
+        None => false,
     }
 }
 
-async fn ingest_file(path: &PathBuf, config: &Config) -> Result<()> {
-    let content = fs::read_to_string(path)
-        .context("Could not read file")?;
+/// Dedupes against the last successful ingest and retries failures with
+/// exponential backoff, persisting progress to the queue so interrupted work
+/// resumes on the next `Run`/`Once` invocation. Acquires a semaphore permit
+/// first so at most `config.concurrency` uploads run at once.
+///
+/// Unlike the Tauri app's raw upload path, this reads the whole file into
+/// memory rather than streaming it: `extract::extractor_for` needs the
+/// complete bytes to parse PDF/DOCX content before anything can be sent, so
+/// there's no chunk-at-a-time body to stream here. `max_file_size_bytes`
+/// bounds that per-file memory use instead.
+async fn ingest_with_retry(path: &str, source: &SourceHandle, ctx: &IngestCtx) -> Result<()> {
+    let _permit = ctx.semaphore.acquire().await.context("Semaphore closed")?;
+
+    let size = source.lock().await.size(path).await?;
+    if size > ctx.config.max_file_size_bytes {
+        println!(
+            "Skipping {}: {} bytes exceeds the {} byte limit",
+            path, size, ctx.config.max_file_size_bytes
+        );
+        return Ok(());
+    }
+
+    let bytes = source.lock().await.read(path).await?;
+    let hash = queue::hash_bytes(&bytes);
 
-    let file_name = path.file_name()
+    if ctx.queue.is_unchanged(path, hash)? {
+        return Ok(());
+    }
+    if ctx.queue.is_backing_off(path)? {
+        return Ok(());
+    }
+
+    ctx.queue.mark_in_flight(path, hash)?;
+
+    println!("Processing: {}", path);
+    match ingest_file(path, &bytes, &ctx.config, &ctx.client).await {
+        Ok(()) => {
+            ctx.queue.mark_succeeded(path, hash)?;
+            Ok(())
+        }
+        Err(e) => {
+            let should_retry = ctx.queue.mark_failed(path, hash)?;
+            if should_retry {
+                eprintln!("Error ingesting file, queued for retry with backoff: {}", e);
+                Ok(())
+            } else {
+                Err(e.context("Exhausted retry attempts"))
+            }
+        }
+    }
+}
+
+async fn ingest_file(path: &str, bytes: &[u8], config: &Config, client: &reqwest::Client) -> Result<()> {
+    let file_name = std::path::Path::new(path)
+        .file_name()
         .context("Could not get file name")?
         .to_string_lossy()
         .to_string();
 
+    let extractor = extract::extractor_for(&file_name)
+        .with_context(|| format!("No extractor registered for {}", path))?;
+    let content = extractor.extract(bytes)?;
+
     let doc_type_hint = guess_doc_type(&file_name, path);
 
     let payload = IngestPayload {
@@ -185,7 +415,6 @@ async fn ingest_file(path: &PathBuf, config: &Config) -> Result<()> {
         content,
     };
 
-    let client = reqwest::Client::new();
     let url = format!("{}/api/ingest", config.backend_url);
 
     let response = client
@@ -205,9 +434,9 @@ async fn ingest_file(path: &PathBuf, config: &Config) -> Result<()> {
     Ok(())
 }
 
-fn guess_doc_type(file_name: &str, path: &PathBuf) -> String {
+fn guess_doc_type(file_name: &str, path: &str) -> String {
     let file_name_lower = file_name.to_lowercase();
-    let path_str = path.to_string_lossy().to_lowercase();
+    let path_str = path.to_lowercase();
 
     if path_str.contains("policy") || path_str.contains("policies") {
         return "POLICY".to_string();
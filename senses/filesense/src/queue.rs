@@ -0,0 +1,156 @@
+//! Durable, at-least-once ingestion queue.
+//!
+//! Tracks, per file path, the content hash and outcome of the last ingest attempt
+//! in a `sled` database next to the config file. This lets `Run`/`Once` skip files
+//! that haven't changed since their last successful upload, and lets failed uploads
+//! be retried with exponential backoff across process restarts.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// Paths are tracked by string key rather than `Path` since queue entries may
+// refer to files on a remote `SourceFs` that don't exist on the local filesystem.
+
+const MAX_ATTEMPTS: u32 = 5;
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IngestStatus {
+    Pending,
+    InFlight,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct QueueEntry {
+    content_hash: u64,
+    status: IngestStatus,
+    attempts: u32,
+    next_attempt_at: u64,
+}
+
+pub struct IngestQueue {
+    db: sled::Db,
+}
+
+impl IngestQueue {
+    pub fn open(path: &Path) -> Result<Self> {
+        let db = sled::open(path)
+            .with_context(|| format!("Could not open ingestion queue at {:?}", path))?;
+        Ok(Self { db })
+    }
+
+    fn get(&self, path: &str) -> Result<Option<QueueEntry>> {
+        match self.db.get(path.as_bytes())? {
+            Some(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn put(&self, path: &str, entry: &QueueEntry) -> Result<()> {
+        self.db.insert(path.as_bytes(), serde_json::to_vec(entry)?)?;
+        self.db.flush()?;
+        Ok(())
+    }
+
+    /// True if `hash` matches the last successful ingest for `path`, i.e. the file
+    /// is unchanged and the upload can be skipped.
+    pub fn is_unchanged(&self, path: &str, hash: u64) -> Result<bool> {
+        Ok(matches!(
+            self.get(path)?,
+            Some(entry) if entry.status == IngestStatus::Succeeded && entry.content_hash == hash
+        ))
+    }
+
+    /// True if a previously failed attempt should not be retried yet: either
+    /// it's still within its backoff window, or it has permanently exhausted
+    /// `MAX_ATTEMPTS` and must never be retried again.
+    pub fn is_backing_off(&self, path: &str) -> Result<bool> {
+        Ok(matches!(
+            self.get(path)?,
+            Some(entry) if entry.status == IngestStatus::Failed
+                && (entry.attempts >= MAX_ATTEMPTS || now_secs() < entry.next_attempt_at)
+        ))
+    }
+
+    pub fn mark_in_flight(&self, path: &str, hash: u64) -> Result<()> {
+        let mut entry = self.get(path)?.unwrap_or_else(|| default_entry(hash));
+        entry.content_hash = hash;
+        entry.status = IngestStatus::InFlight;
+        self.put(path, &entry)
+    }
+
+    pub fn mark_succeeded(&self, path: &str, hash: u64) -> Result<()> {
+        let mut entry = self.get(path)?.unwrap_or_else(|| default_entry(hash));
+        entry.content_hash = hash;
+        entry.status = IngestStatus::Succeeded;
+        entry.attempts = 0;
+        self.put(path, &entry)
+    }
+
+    /// Records a failed attempt and schedules the next retry with exponential backoff.
+    /// Returns `true` if `MAX_ATTEMPTS` has not yet been exhausted and the caller
+    /// should keep retrying.
+    pub fn mark_failed(&self, path: &str, hash: u64) -> Result<bool> {
+        let mut entry = self.get(path)?.unwrap_or_else(|| default_entry(hash));
+        entry.content_hash = hash;
+        entry.attempts += 1;
+        entry.status = IngestStatus::Failed;
+        entry.next_attempt_at = now_secs() + backoff_for(entry.attempts).as_secs();
+        self.put(path, &entry)?;
+        Ok(entry.attempts < MAX_ATTEMPTS)
+    }
+
+    /// Paths left `Pending`/`InFlight`/due-for-retry from a previous run, so the
+    /// watcher can resume outstanding work on startup. Excludes `Failed` paths
+    /// that have exhausted `MAX_ATTEMPTS`, which must not be resubmitted.
+    pub fn resumable_paths(&self) -> Result<Vec<String>> {
+        let mut paths = Vec::new();
+        for item in self.db.iter() {
+            let (key, value) = item?;
+            let entry: QueueEntry = serde_json::from_slice(&value)?;
+            let due = matches!(entry.status, IngestStatus::Pending | IngestStatus::InFlight)
+                || (entry.status == IngestStatus::Failed
+                    && entry.attempts < MAX_ATTEMPTS
+                    && now_secs() >= entry.next_attempt_at);
+            if due {
+                paths.push(String::from_utf8_lossy(&key).to_string());
+            }
+        }
+        Ok(paths)
+    }
+}
+
+fn default_entry(hash: u64) -> QueueEntry {
+    QueueEntry {
+        content_hash: hash,
+        status: IngestStatus::Pending,
+        attempts: 0,
+        next_attempt_at: 0,
+    }
+}
+
+fn backoff_for(attempts: u32) -> Duration {
+    let secs = BASE_BACKOFF.as_secs().saturating_mul(1u64 << attempts.min(6));
+    Duration::from_secs(secs).min(MAX_BACKOFF)
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+pub fn hash_bytes(bytes: &[u8]) -> u64 {
+    xxhash_rust::xxh3::xxh3_64(bytes)
+}
+
+pub fn default_queue_path() -> Result<PathBuf> {
+    let dir = dirs::home_dir().context("Could not find home directory")?;
+    Ok(dir.join(".opsense_filesense_queue"))
+}
@@ -0,0 +1,205 @@
+//! Abstracts over where a configured folder actually lives, so the watcher's
+//! traversal and the ingester's reads don't need to know whether they're
+//! talking to the local filesystem or a server reachable over SFTP.
+
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use std::io::Read;
+use std::net::TcpStream;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// Not `Sync`: callers only ever reach an implementation through the
+/// `tokio::sync::Mutex`-guarded `SourceHandle`, which serializes access and
+/// only requires `Send`. `SftpFs` in particular wraps a libssh2 session that
+/// isn't safe to call concurrently from multiple threads.
+#[async_trait]
+pub trait SourceFs: Send {
+    async fn read_dir(&self, path: &str) -> Result<Vec<String>>;
+    async fn is_dir(&self, path: &str) -> Result<bool>;
+    async fn read(&self, path: &str) -> Result<Vec<u8>>;
+    async fn size(&self, path: &str) -> Result<u64>;
+}
+
+pub struct LocalFs;
+
+#[async_trait]
+impl SourceFs for LocalFs {
+    async fn read_dir(&self, path: &str) -> Result<Vec<String>> {
+        let mut entries = Vec::new();
+        for entry in std::fs::read_dir(path).with_context(|| format!("Could not list {}", path))? {
+            entries.push(entry?.path().to_string_lossy().to_string());
+        }
+        Ok(entries)
+    }
+
+    async fn is_dir(&self, path: &str) -> Result<bool> {
+        Ok(Path::new(path).is_dir())
+    }
+
+    async fn read(&self, path: &str) -> Result<Vec<u8>> {
+        std::fs::read(path).with_context(|| format!("Could not read {}", path))
+    }
+
+    async fn size(&self, path: &str) -> Result<u64> {
+        let metadata = std::fs::metadata(path).with_context(|| format!("Could not stat {}", path))?;
+        Ok(metadata.len())
+    }
+}
+
+/// Credentials for an SFTP source, taken from `Config`.
+pub struct SftpAuth {
+    pub username: String,
+    pub password: Option<String>,
+    pub key_path: Option<String>,
+}
+
+/// An authenticated SFTP connection. Kept open for the lifetime of a scan so
+/// repeated `read_dir`/`read` calls don't each pay a fresh handshake.
+///
+/// `sftp` is wrapped in an `Arc<Mutex<_>>` purely so each call can move an
+/// owned handle into `tokio::task::spawn_blocking`, which requires `'static`;
+/// the mutex is never contended in practice since `SourceHandle` already
+/// serializes calls into a given `SftpFs`.
+pub struct SftpFs {
+    sftp: Arc<Mutex<ssh2::Sftp>>,
+    // Held only to keep the session (and its TCP stream) alive as long as `sftp`.
+    _session: ssh2::Session,
+}
+
+impl SftpFs {
+    pub fn connect(host: &str, port: u16, auth: &SftpAuth) -> Result<Self> {
+        let tcp = TcpStream::connect((host, port))
+            .with_context(|| format!("Could not connect to {}:{}", host, port))?;
+
+        let mut session = ssh2::Session::new().context("Could not create SSH session")?;
+        session.set_tcp_stream(tcp);
+        session.handshake().context("SSH handshake failed")?;
+
+        if let Some(key_path) = &auth.key_path {
+            session
+                .userauth_pubkey_file(&auth.username, None, Path::new(key_path), None)
+                .context("SSH public key authentication failed")?;
+        } else if let Some(password) = &auth.password {
+            session
+                .userauth_password(&auth.username, password)
+                .context("SSH password authentication failed")?;
+        } else {
+            return Err(anyhow!(
+                "No SFTP credentials configured for {}@{} (set sftp_key_path or sftp_password)",
+                auth.username,
+                host
+            ));
+        }
+
+        let sftp = session.sftp().context("Could not start SFTP subsystem")?;
+        Ok(Self {
+            sftp: Arc::new(Mutex::new(sftp)),
+            _session: session,
+        })
+    }
+}
+
+#[async_trait]
+impl SourceFs for SftpFs {
+    async fn read_dir(&self, path: &str) -> Result<Vec<String>> {
+        let sftp = Arc::clone(&self.sftp);
+        let path = path.to_string();
+        tokio::task::spawn_blocking(move || -> Result<Vec<String>> {
+            let sftp = sftp.lock().unwrap();
+            let entries = sftp
+                .readdir(Path::new(&path))
+                .with_context(|| format!("Could not list {} over SFTP", path))?;
+            Ok(entries
+                .into_iter()
+                .map(|(entry_path, _)| entry_path.to_string_lossy().to_string())
+                .collect())
+        })
+        .await
+        .context("SFTP read_dir task panicked")?
+    }
+
+    async fn is_dir(&self, path: &str) -> Result<bool> {
+        let sftp = Arc::clone(&self.sftp);
+        let path = path.to_string();
+        tokio::task::spawn_blocking(move || -> Result<bool> {
+            let sftp = sftp.lock().unwrap();
+            let stat = sftp
+                .stat(Path::new(&path))
+                .with_context(|| format!("Could not stat {} over SFTP", path))?;
+            Ok(stat.is_dir())
+        })
+        .await
+        .context("SFTP is_dir task panicked")?
+    }
+
+    async fn read(&self, path: &str) -> Result<Vec<u8>> {
+        let sftp = Arc::clone(&self.sftp);
+        let path = path.to_string();
+        tokio::task::spawn_blocking(move || -> Result<Vec<u8>> {
+            let sftp = sftp.lock().unwrap();
+            let mut file = sftp
+                .open(Path::new(&path))
+                .with_context(|| format!("Could not open {} over SFTP", path))?;
+            let mut buf = Vec::new();
+            file.read_to_end(&mut buf)
+                .with_context(|| format!("Could not read {} over SFTP", path))?;
+            Ok(buf)
+        })
+        .await
+        .context("SFTP read task panicked")?
+    }
+
+    async fn size(&self, path: &str) -> Result<u64> {
+        let sftp = Arc::clone(&self.sftp);
+        let path = path.to_string();
+        tokio::task::spawn_blocking(move || -> Result<u64> {
+            let sftp = sftp.lock().unwrap();
+            let stat = sftp
+                .stat(Path::new(&path))
+                .with_context(|| format!("Could not stat {} over SFTP", path))?;
+            stat.size
+                .with_context(|| format!("SFTP server did not report a size for {}", path))
+        })
+        .await
+        .context("SFTP size task panicked")?
+    }
+}
+
+/// A folder entry parsed out of `Config.folders`.
+pub enum FolderSource {
+    Local {
+        root: String,
+    },
+    Sftp {
+        host: String,
+        port: u16,
+        username: String,
+        root: String,
+    },
+}
+
+/// Parses a folder entry. Remote entries look like `sftp://user@host[:port]/path`;
+/// anything else is treated as a local path.
+pub fn parse_folder(folder: &str) -> FolderSource {
+    if let Some(rest) = folder.strip_prefix("sftp://") {
+        if let Some((userhost, root)) = rest.split_once('/') {
+            let (user, hostport) = userhost.split_once('@').unwrap_or(("", userhost));
+            let (host, port) = match hostport.split_once(':') {
+                Some((host, port)) => (host, port.parse().unwrap_or(22)),
+                None => (hostport, 22),
+            };
+
+            return FolderSource::Sftp {
+                host: host.to_string(),
+                port,
+                username: user.to_string(),
+                root: format!("/{}", root),
+            };
+        }
+    }
+
+    FolderSource::Local {
+        root: folder.to_string(),
+    }
+}